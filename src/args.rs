@@ -40,4 +40,8 @@ pub struct RemoveArgs {
 #[derive(Debug, clap::Args)]
 pub struct PrintArgs {
     pub png_file: PathBuf,
+
+    /// Keep reading past corrupt chunks instead of aborting on the first one.
+    #[arg(long)]
+    pub lossy: bool,
 }
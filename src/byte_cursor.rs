@@ -0,0 +1,166 @@
+use std::error;
+use std::fmt::Display;
+
+/// A bounds-checked, read-only cursor over a byte slice.
+///
+/// Every read tracks how far into the original slice it started, so a
+/// short read reports exactly where it ran out instead of bubbling up an
+/// opaque `io::Error`.
+#[derive(Debug, Clone, Copy)]
+pub struct ByteCursor<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> ByteCursor<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    /// How many bytes into the original slice this cursor currently sits.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// How many bytes are left to read.
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() - self.offset
+    }
+
+    /// Everything from the current offset to the end of the slice.
+    pub fn rest(&self) -> &'a [u8] {
+        &self.bytes[self.offset..]
+    }
+
+    /// Advances past and returns the next `len` bytes.
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8], NotEnoughBytesError> {
+        let slice = self
+            .bytes
+            .get(self.offset..self.offset + len)
+            .ok_or_else(|| NotEnoughBytesError::new(len, self.remaining(), self.offset))?;
+        self.offset += len;
+        Ok(slice)
+    }
+
+    /// Advances past and decodes the next 4 bytes as a big-endian `u32`.
+    pub fn u32_be(&mut self) -> Result<u32, NotEnoughBytesError> {
+        self.take(4).map(|bytes| {
+            u32::from_be_bytes(bytes.try_into().expect("take(4) returns 4 bytes"))
+        })
+    }
+
+    /// Advances past and decodes the next 2 bytes as a big-endian `u16`.
+    pub fn u16_be(&mut self) -> Result<u16, NotEnoughBytesError> {
+        self.take(2).map(|bytes| {
+            u16::from_be_bytes(bytes.try_into().expect("take(2) returns 2 bytes"))
+        })
+    }
+
+    /// Advances past and returns the next 4 bytes as a chunk-type fourcc.
+    pub fn fourcc(&mut self) -> Result<[u8; 4], NotEnoughBytesError> {
+        self.take(4)
+            .map(|bytes| bytes.try_into().expect("take(4) returns 4 bytes"))
+    }
+}
+
+#[derive(Debug)]
+pub struct NotEnoughBytesError {
+    needed: usize,
+    available: usize,
+    offset: usize,
+}
+
+impl NotEnoughBytesError {
+    fn new(needed: usize, available: usize, offset: usize) -> Self {
+        Self {
+            needed,
+            available,
+            offset,
+        }
+    }
+
+    pub fn needed(&self) -> usize {
+        self.needed
+    }
+
+    pub fn available(&self) -> usize {
+        self.available
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns an equivalent error whose `offset` is relative to the start
+    /// of some larger slice that this error's `ByteCursor` only covered a
+    /// piece of — e.g. when a cursor is built over one chunk's remainder
+    /// of a file, shifting by that chunk's starting position turns a
+    /// chunk-relative offset into a file one.
+    pub fn with_base_offset(self, base: usize) -> Self {
+        Self {
+            offset: self.offset + base,
+            ..self
+        }
+    }
+}
+
+impl Display for NotEnoughBytesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "needed {} bytes at offset {}, only {} available",
+            self.needed, self.offset, self.available
+        )
+    }
+}
+
+impl error::Error for NotEnoughBytesError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take() {
+        let mut cursor = ByteCursor::new(&[1, 2, 3, 4, 5]);
+        assert_eq!(cursor.take(2).unwrap(), &[1, 2]);
+        assert_eq!(cursor.offset(), 2);
+        assert_eq!(cursor.take(3).unwrap(), &[3, 4, 5]);
+        assert_eq!(cursor.remaining(), 0);
+    }
+
+    #[test]
+    fn test_take_not_enough_bytes() {
+        let mut cursor = ByteCursor::new(&[1, 2, 3]);
+        cursor.take(1).unwrap();
+        let err = cursor.take(10).unwrap_err();
+        assert_eq!(err.needed(), 10);
+        assert_eq!(err.available(), 2);
+        assert_eq!(err.offset(), 1);
+    }
+
+    #[test]
+    fn test_u32_be() {
+        let mut cursor = ByteCursor::new(&[0, 0, 1, 0]);
+        assert_eq!(cursor.u32_be().unwrap(), 256);
+    }
+
+    #[test]
+    fn test_u16_be() {
+        let mut cursor = ByteCursor::new(&[1, 0]);
+        assert_eq!(cursor.u16_be().unwrap(), 256);
+    }
+
+    #[test]
+    fn test_fourcc() {
+        let mut cursor = ByteCursor::new(b"IHDR");
+        assert_eq!(cursor.fourcc().unwrap(), *b"IHDR");
+    }
+
+    #[test]
+    fn test_rest() {
+        let mut cursor = ByteCursor::new(&[1, 2, 3, 4]);
+        cursor.take(1).unwrap();
+        assert_eq!(cursor.rest(), &[2, 3, 4]);
+    }
+}
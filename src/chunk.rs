@@ -1,7 +1,8 @@
 use std::error;
 use std::fmt::Display;
-use std::io::{self, BufReader, Read};
+use std::io::{self, Write};
 
+use crate::byte_cursor::{ByteCursor, NotEnoughBytesError};
 use crate::chunk_type::{ChunkType, TryFromChunkTypeError};
 use crate::Result;
 
@@ -44,15 +45,21 @@ impl Chunk {
         Ok(String::from_utf8(self.data.clone())?)
     }
 
+    /// Streams this chunk's bytes (length, type, data, crc) directly to
+    /// `writer`, without building an intermediate buffer.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.len.to_be_bytes())?;
+        writer.write_all(self.chunk_type.as_slice())?;
+        writer.write_all(&self.data)?;
+        writer.write_all(&self.crc.to_be_bytes())?;
+        Ok(())
+    }
+
     pub fn as_bytes(&self) -> Vec<u8> {
-        self.len
-            .to_be_bytes()
-            .iter()
-            .chain(self.chunk_type.as_slice().iter())
-            .chain(&self.data)
-            .chain(self.crc.to_be_bytes().iter())
-            .copied()
-            .collect()
+        let mut bytes = Vec::with_capacity(12 + self.data.len());
+        self.write_to(&mut bytes)
+            .expect("writing to a Vec<u8> never fails");
+        bytes
     }
 }
 
@@ -84,6 +91,30 @@ impl TryFromBytesError {
     fn new(kind: TryFromBytesErrorKind) -> Self {
         Self { kind }
     }
+
+    /// How many bytes to skip, from the start of this chunk, to reach the
+    /// next plausible chunk boundary — `None` if the input ran out before
+    /// the chunk was complete, so no further boundary lies within it.
+    pub fn recover(&self) -> Option<usize> {
+        match &self.kind {
+            TryFromBytesErrorKind::ChunkType { recover, .. } => Some(*recover),
+            TryFromBytesErrorKind::CorruptCrc { recover, .. } => Some(*recover),
+            TryFromBytesErrorKind::NotEnoughBytes(_) => None,
+        }
+    }
+
+    /// Rewrites a wrapped [`NotEnoughBytesError`]'s offset to be relative
+    /// to `base` instead of the start of the slice this chunk was parsed
+    /// from — callers that parse chunk-by-chunk out of a larger file use
+    /// this to report file-relative, not chunk-relative, positions.
+    pub(crate) fn with_base_offset(self, base: usize) -> Self {
+        match self.kind {
+            TryFromBytesErrorKind::NotEnoughBytes(err) => Self::new(
+                TryFromBytesErrorKind::NotEnoughBytes(err.with_base_offset(base)),
+            ),
+            kind => Self::new(kind),
+        }
+    }
 }
 
 impl Display for TryFromBytesError {
@@ -101,22 +132,27 @@ impl error::Error for TryFromBytesError {
 #[derive(Debug)]
 #[non_exhaustive]
 enum TryFromBytesErrorKind {
-    ChunkType(TryFromChunkTypeError),
+    ChunkType {
+        source: TryFromChunkTypeError,
+        recover: usize,
+    },
     CorruptCrc {
         calculated: u32,
         expected: u32,
+        recover: usize,
     },
     #[non_exhaustive]
-    NotEnoughBytes(io::Error),
+    NotEnoughBytes(NotEnoughBytesError),
 }
 
 impl Display for TryFromBytesErrorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            TryFromBytesErrorKind::ChunkType(err) => Display::fmt(err, f),
+            TryFromBytesErrorKind::ChunkType { source, .. } => Display::fmt(source, f),
             TryFromBytesErrorKind::CorruptCrc {
                 calculated,
                 expected,
+                ..
             } => write!(f, "invalid crc: expected: {expected}, got {calculated}"),
             TryFromBytesErrorKind::NotEnoughBytes(err) => Display::fmt(err, f),
         }
@@ -125,53 +161,39 @@ impl Display for TryFromBytesErrorKind {
 
 impl error::Error for TryFromBytesErrorKind {}
 
-impl From<TryFromChunkTypeError> for TryFromBytesError {
-    fn from(err: TryFromChunkTypeError) -> Self {
-        Self::new(TryFromBytesErrorKind::ChunkType(err))
-    }
-}
-
 impl TryFrom<&[u8]> for Chunk {
     type Error = TryFromBytesError;
 
     fn try_from(bytes: &[u8]) -> std::result::Result<Self, Self::Error> {
-        let mut bytes = BufReader::new(bytes);
-        let mut buffer = [0u8; 4];
-        bytes
-            .read_exact(&mut buffer)
-            .map_err(|e| TryFromBytesError::new(TryFromBytesErrorKind::NotEnoughBytes(e)))?;
-        let len = u32::from_be_bytes(buffer);
+        let mut cursor = ByteCursor::new(bytes);
 
-        bytes
-            .read_exact(&mut buffer)
+        let len = cursor
+            .u32_be()
             .map_err(|e| TryFromBytesError::new(TryFromBytesErrorKind::NotEnoughBytes(e)))?;
-        let chunk_type = ChunkType::try_from(buffer)?;
-
-        if len == 0 {
-            let crc = calculate_crc(&chunk_type, &[]);
-            return Ok(Self {
-                len,
-                chunk_type,
-                data: Vec::new(),
-                crc,
-            });
-        }
+        // Bytes to the next chunk boundary: length + type + data + crc.
+        let recover = 12usize.saturating_add(len as usize);
 
-        let mut data = vec![0u8; len as usize];
-        bytes
-            .read_exact(&mut data)
+        let type_bytes = cursor
+            .fourcc()
             .map_err(|e| TryFromBytesError::new(TryFromBytesErrorKind::NotEnoughBytes(e)))?;
+        let chunk_type = ChunkType::try_from(type_bytes).map_err(|source| {
+            TryFromBytesError::new(TryFromBytesErrorKind::ChunkType { source, recover })
+        })?;
 
-        let crc = calculate_crc(&chunk_type, &data[..]);
+        let data = cursor
+            .take(len as usize)
+            .map_err(|e| TryFromBytesError::new(TryFromBytesErrorKind::NotEnoughBytes(e)))?
+            .to_vec();
+        let crc = calculate_crc(&chunk_type, &data);
 
-        bytes
-            .read_exact(&mut buffer)
+        let crc_given = cursor
+            .u32_be()
             .map_err(|e| TryFromBytesError::new(TryFromBytesErrorKind::NotEnoughBytes(e)))?;
-        let crc_given = u32::from_be_bytes(buffer);
         if crc != crc_given {
             return Err(TryFromBytesError::new(TryFromBytesErrorKind::CorruptCrc {
                 calculated: crc,
                 expected: crc_given,
+                recover,
             }));
         }
         Ok(Self {
@@ -244,6 +266,15 @@ mod tests {
         assert_eq!(chunk.crc(), 2882656334);
     }
 
+    #[test]
+    fn test_write_to_matches_as_bytes() {
+        let chunk = testing_chunk();
+        let mut written = Vec::new();
+        chunk.write_to(&mut written).unwrap();
+
+        assert_eq!(written, chunk.as_bytes());
+    }
+
     #[test]
     fn test_valid_chunk_from_bytes() {
         let data_length: u32 = 42;
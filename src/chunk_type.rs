@@ -3,7 +3,7 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::{self, FromStr};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct ChunkType {
     bytes: [u8; 4],
 }
@@ -1,12 +1,12 @@
 use std::error::Error;
 use std::fmt::Display;
-use std::fs;
-use std::io;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
 use std::path::Path;
 
 use crate::chunk::Chunk;
 use crate::chunk_type::ChunkType;
-use crate::png::{self, Png};
+use crate::png::{self, Png, StandardChunk};
 use crate::AnyError;
 
 pub fn encode(path: &Path, chunk_type: &str, message: &str) -> Result<(), AnyError> {
@@ -16,7 +16,9 @@ pub fn encode(path: &Path, chunk_type: &str, message: &str) -> Result<(), AnyErr
         message.as_bytes().to_owned(),
     );
     png.append_chunk(chunk);
-    fs::write(path, png.as_bytes())?;
+    let mut writer = BufWriter::new(File::create(path)?);
+    png.write_to(&mut writer)?;
+    writer.flush()?;
     Ok(())
 }
 
@@ -35,18 +37,43 @@ pub fn decode(path: &Path, chunk_type: &str) -> Result<(), AnyError> {
 pub fn remove(path: &Path, chunk_type: &str) -> Result<(), AnyError> {
     let mut png = read_png(path)?;
     png.remove_first_chunk(chunk_type)?;
-    fs::write(path, png.as_bytes())?;
+    let mut writer = BufWriter::new(File::create(path)?);
+    png.write_to(&mut writer)?;
+    writer.flush()?;
     Ok(())
 }
 
-pub fn print(path: &Path) -> Result<(), ReadPngError> {
-    let png = read_png(path)?;
-    for chunk in png.chunks() {
-        println!("{chunk:#}")
+pub fn print(path: &Path, lossy: bool) -> Result<(), ReadPngError> {
+    if !lossy {
+        let png = read_png(path)?;
+        print_chunks(&png);
+        return Ok(());
+    }
+
+    let contents = fs::read(path)?;
+    let (png, errors) = Png::try_from_lossy(&contents);
+    for err in &errors {
+        eprint!("[!] chunk error: {err}");
+        let mut source = Error::source(err);
+        while let Some(cause) = source {
+            eprint!(": {cause}");
+            source = cause.source();
+        }
+        eprintln!();
     }
+    print_chunks(&png);
     Ok(())
 }
 
+fn print_chunks(png: &Png) {
+    for chunk in png.chunks() {
+        match StandardChunk::parse(chunk) {
+            Some(Ok(standard)) => println!("{standard}"),
+            Some(Err(_)) | None => println!("{chunk:#}"),
+        }
+    }
+}
+
 fn read_png(path: &Path) -> Result<Png, ReadPngError> {
     let contents = fs::read(path)?;
     let png = Png::try_from(&contents[..])?;
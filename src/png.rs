@@ -0,0 +1,1075 @@
+use std::error;
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use crate::byte_cursor::ByteCursor;
+use crate::chunk::{Chunk, TryFromBytesError};
+use crate::chunk_type::ChunkType;
+use crate::Result;
+
+const STANDARD_HEADER: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+pub struct Png {
+    chunks: Vec<Chunk>,
+}
+
+impl Png {
+    pub fn from_chunks(chunks: Vec<Chunk>) -> Self {
+        Self { chunks }
+    }
+
+    pub fn append_chunk(&mut self, chunk: Chunk) {
+        self.chunks.push(chunk);
+    }
+
+    pub fn remove_first_chunk(&mut self, chunk_type: &str) -> Result<Chunk> {
+        let pos = self
+            .chunks
+            .iter()
+            .position(|chunk| chunk.chunk_type().to_string() == chunk_type)
+            .ok_or_else(|| format!("chunk type `{chunk_type}` not found"))?;
+        Ok(self.chunks.remove(pos))
+    }
+
+    pub fn signature(&self) -> &[u8; 8] {
+        &STANDARD_HEADER
+    }
+
+    /// Decodes the leading `IHDR` chunk, validating that the stream
+    /// starts with `IHDR` and ends with `IEND` along the way.
+    pub fn header(&self) -> Result<Ihdr> {
+        let first = self.chunks.first().ok_or("png has no chunks")?;
+        if first.chunk_type().to_string() != "IHDR" {
+            return Err("png does not start with an IHDR chunk".into());
+        }
+        let last = self.chunks.last().ok_or("png has no chunks")?;
+        if last.chunk_type().to_string() != "IEND" {
+            return Err("png does not end with an IEND chunk".into());
+        }
+        Ok(Ihdr::try_from(first)?)
+    }
+
+    pub fn chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+
+    pub fn chunk_by_type(&self, chunk_type: &str) -> Option<&Chunk> {
+        self.chunks
+            .iter()
+            .find(|chunk| chunk.chunk_type().to_string() == chunk_type)
+    }
+
+    /// Streams the signature followed by every chunk directly to
+    /// `writer`, without building an intermediate buffer.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&STANDARD_HEADER)?;
+        for chunk in &self.chunks {
+            chunk.write_to(writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let capacity = STANDARD_HEADER.len()
+            + self
+                .chunks
+                .iter()
+                .map(|chunk| 12 + chunk.length() as usize)
+                .sum::<usize>();
+        let mut bytes = Vec::with_capacity(capacity);
+        self.write_to(&mut bytes)
+            .expect("writing to a Vec<u8> never fails");
+        bytes
+    }
+
+    /// Like [`Png::try_from`], but a corrupt chunk doesn't abort the whole
+    /// parse: its error is recorded, enough bytes are skipped to reach the
+    /// next plausible chunk boundary, and parsing continues through
+    /// `IEND`. Useful for inspecting a partially-damaged file.
+    pub fn try_from_lossy(bytes: &[u8]) -> (Self, Vec<TryFromBytesError>) {
+        let mut chunks = Vec::new();
+        let mut errors = Vec::new();
+
+        let mut cursor = ByteCursor::new(bytes);
+        let Ok(header) = cursor.take(STANDARD_HEADER.len()) else {
+            return (Self { chunks }, errors);
+        };
+        if header != STANDARD_HEADER.as_slice() {
+            return (Self { chunks }, errors);
+        }
+
+        while cursor.remaining() > 0 {
+            let chunk_offset = cursor.offset();
+            match Chunk::try_from(cursor.rest()).map_err(|e| e.with_base_offset(chunk_offset)) {
+                Ok(chunk) => {
+                    let consumed = chunk.length() as usize + 12;
+                    let is_end = chunk.chunk_type().to_string() == "IEND";
+                    cursor
+                        .take(consumed)
+                        .expect("Chunk::try_from already validated this many bytes");
+                    chunks.push(chunk);
+                    if is_end {
+                        break;
+                    }
+                }
+                Err(err) => {
+                    let Some(recover) = err.recover() else {
+                        errors.push(err);
+                        break;
+                    };
+                    let _ = cursor.take(recover.min(cursor.remaining()));
+                    errors.push(err);
+                }
+            }
+        }
+
+        (Self { chunks }, errors)
+    }
+}
+
+impl TryFrom<&[u8]> for Png {
+    type Error = TryFromSliceError;
+
+    fn try_from(bytes: &[u8]) -> std::result::Result<Self, Self::Error> {
+        let mut cursor = ByteCursor::new(bytes);
+        let header = cursor
+            .take(STANDARD_HEADER.len())
+            .map_err(|_| TryFromSliceError::new(TryFromSliceErrorKind::InvalidHeader))?;
+        if header != STANDARD_HEADER.as_slice() {
+            return Err(TryFromSliceError::new(TryFromSliceErrorKind::InvalidHeader));
+        }
+
+        let mut chunks = Vec::new();
+        while cursor.remaining() > 0 {
+            let chunk_offset = cursor.offset();
+            let chunk =
+                Chunk::try_from(cursor.rest()).map_err(|e| e.with_base_offset(chunk_offset))?;
+            let consumed = chunk.length() as usize + 12;
+            cursor
+                .take(consumed)
+                .expect("Chunk::try_from already validated this many bytes");
+            chunks.push(chunk);
+        }
+
+        Ok(Self { chunks })
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct TryFromSliceError {
+    kind: TryFromSliceErrorKind,
+}
+
+impl TryFromSliceError {
+    fn new(kind: TryFromSliceErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl Display for TryFromSliceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cannot convert to Png")
+    }
+}
+
+impl error::Error for TryFromSliceError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.kind)
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+enum TryFromSliceErrorKind {
+    InvalidHeader,
+    Chunk(TryFromBytesError),
+}
+
+impl Display for TryFromSliceErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TryFromSliceErrorKind::InvalidHeader => {
+                write!(f, "file does not start with the png header")
+            }
+            TryFromSliceErrorKind::Chunk(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl error::Error for TryFromSliceErrorKind {}
+
+impl From<TryFromBytesError> for TryFromSliceError {
+    fn from(err: TryFromBytesError) -> Self {
+        Self::new(TryFromSliceErrorKind::Chunk(err))
+    }
+}
+
+/// Scratch space `StreamDecoder` uses while it waits for a fixed-size
+/// field (length, type or crc) to arrive across one or more calls. Those
+/// fields are never more than 4 bytes wide.
+const SCRATCH_CAPACITY: usize = 4;
+
+/// Upper bound on how much of a chunk's declared (and untrusted) length
+/// `StreamDecoder` will reserve up front for its data buffer; the rest is
+/// grown incrementally as real bytes are pushed.
+const DATA_RESERVE_CAP: usize = 32 * 1024;
+
+/// A push-based, incremental counterpart to [`Png::try_from`] for callers
+/// that receive bytes over time (stdin, a socket, a mmap'd window) instead
+/// of holding the whole file in memory.
+///
+/// Feed it bytes with [`StreamDecoder::decode_next`] as they arrive; it
+/// never discards state on a short read, so a chunk whose data straddles
+/// two calls just picks up where it left off on the next one.
+pub struct StreamDecoder {
+    state: State,
+    scratch: Vec<u8>,
+    length: u32,
+    chunk_type: Option<ChunkType>,
+    data: Vec<u8>,
+    poisoned: bool,
+}
+
+enum State {
+    Signature(usize),
+    Length,
+    Type,
+    Data(usize),
+    Crc,
+}
+
+/// One step of progress made by [`StreamDecoder::decode_next`].
+pub enum Decoded {
+    /// All of the input was consumed (and buffered) without completing
+    /// anything that produces an event.
+    Nothing,
+    /// The length and type of a chunk have been read; its data is still
+    /// to come.
+    ChunkBegin { length: u32, chunk_type: ChunkType },
+    /// A chunk's data and CRC have both arrived and the CRC checked out.
+    ChunkComplete(Chunk),
+    /// The `IEND` chunk was just completed.
+    ImageEnd,
+}
+
+impl StreamDecoder {
+    pub fn new() -> Self {
+        Self {
+            state: State::Signature(0),
+            scratch: Vec::with_capacity(SCRATCH_CAPACITY),
+            length: 0,
+            chunk_type: None,
+            data: Vec::new(),
+            poisoned: false,
+        }
+    }
+
+    /// Consumes as many bytes of `input` as it can, returning how many
+    /// were consumed along with the [`Decoded`] event that resulted.
+    ///
+    /// Call this again with the remainder of `input` (or with newly
+    /// arrived bytes) until it reports [`Decoded::ImageEnd`]. Once this
+    /// returns `Err`, the decoder is poisoned: its internal state no
+    /// longer corresponds to a chunk boundary, so every subsequent call
+    /// returns [`StreamDecodeErrorKind::Poisoned`] instead of resuming.
+    pub fn decode_next(&mut self, input: &[u8]) -> Result<(usize, Decoded)> {
+        if self.poisoned {
+            return Err(StreamDecodeError::new(StreamDecodeErrorKind::Poisoned).into());
+        }
+
+        let result = self.decode_next_inner(input);
+        if result.is_err() {
+            self.poisoned = true;
+        }
+        result
+    }
+
+    fn decode_next_inner(&mut self, input: &[u8]) -> Result<(usize, Decoded)> {
+        let mut consumed = 0;
+
+        loop {
+            match &mut self.state {
+                State::Signature(pos) => {
+                    while *pos < STANDARD_HEADER.len() {
+                        let Some(&byte) = input.get(consumed) else {
+                            return Ok((consumed, Decoded::Nothing));
+                        };
+                        if byte != STANDARD_HEADER[*pos] {
+                            return Err(StreamDecodeError::new(
+                                StreamDecodeErrorKind::InvalidSignature { offset: *pos, byte },
+                            )
+                            .into());
+                        }
+                        *pos += 1;
+                        consumed += 1;
+                    }
+                    self.state = State::Length;
+                }
+                State::Length => {
+                    if !self.fill_scratch(input, &mut consumed, 4) {
+                        return Ok((consumed, Decoded::Nothing));
+                    }
+                    let mut bytes = [0u8; 4];
+                    bytes.copy_from_slice(&self.scratch);
+                    self.length = u32::from_be_bytes(bytes);
+                    self.scratch.clear();
+                    self.state = State::Type;
+                }
+                State::Type => {
+                    if !self.fill_scratch(input, &mut consumed, 4) {
+                        return Ok((consumed, Decoded::Nothing));
+                    }
+                    let mut bytes = [0u8; 4];
+                    bytes.copy_from_slice(&self.scratch);
+                    self.scratch.clear();
+                    let chunk_type = ChunkType::try_from(bytes)?;
+                    self.chunk_type = Some(chunk_type);
+                    // `self.length` is read straight off the wire and is not
+                    // trusted: reserving it verbatim would let a hostile
+                    // 12-byte header (length = u32::MAX) force a multi-GiB
+                    // allocation before a single data byte has arrived. Cap
+                    // the upfront reservation and let it grow only as real
+                    // bytes are pushed via `extend_from_slice`.
+                    self.data = Vec::with_capacity((self.length as usize).min(DATA_RESERVE_CAP));
+                    self.state = State::Data(self.length as usize);
+                    return Ok((
+                        consumed,
+                        Decoded::ChunkBegin {
+                            length: self.length,
+                            chunk_type,
+                        },
+                    ));
+                }
+                State::Data(remaining) => {
+                    if *remaining > 0 {
+                        let available = input.len() - consumed;
+                        if available == 0 {
+                            return Ok((consumed, Decoded::Nothing));
+                        }
+                        let take = available.min(*remaining);
+                        self.data.extend_from_slice(&input[consumed..consumed + take]);
+                        consumed += take;
+                        *remaining -= take;
+                        if *remaining > 0 {
+                            return Ok((consumed, Decoded::Nothing));
+                        }
+                    }
+                    self.state = State::Crc;
+                }
+                State::Crc => {
+                    if !self.fill_scratch(input, &mut consumed, 4) {
+                        return Ok((consumed, Decoded::Nothing));
+                    }
+                    let mut bytes = [0u8; 4];
+                    bytes.copy_from_slice(&self.scratch);
+                    self.scratch.clear();
+                    let expected = u32::from_be_bytes(bytes);
+
+                    let chunk_type = self.chunk_type.take().expect("set in State::Type");
+                    let data = std::mem::take(&mut self.data);
+                    let chunk = Chunk::new(chunk_type, data);
+                    if chunk.crc() != expected {
+                        return Err(StreamDecodeError::new(StreamDecodeErrorKind::CorruptCrc {
+                            calculated: chunk.crc(),
+                            expected,
+                        })
+                        .into());
+                    }
+
+                    self.state = State::Length;
+                    if chunk_type.to_string() == "IEND" {
+                        return Ok((consumed, Decoded::ImageEnd));
+                    }
+                    return Ok((consumed, Decoded::ChunkComplete(chunk)));
+                }
+            }
+        }
+    }
+
+    /// Accumulates bytes from `input` into `self.scratch` until it holds
+    /// `needed` bytes, advancing `consumed` as it goes. Returns `true`
+    /// once `needed` bytes are available.
+    fn fill_scratch(&mut self, input: &[u8], consumed: &mut usize, needed: usize) -> bool {
+        while self.scratch.len() < needed {
+            match input.get(*consumed) {
+                Some(&byte) => {
+                    self.scratch.push(byte);
+                    *consumed += 1;
+                }
+                None => return false,
+            }
+        }
+        true
+    }
+}
+
+impl Default for StreamDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug)]
+pub struct StreamDecodeError {
+    kind: StreamDecodeErrorKind,
+}
+
+impl StreamDecodeError {
+    fn new(kind: StreamDecodeErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl Display for StreamDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.kind, f)
+    }
+}
+
+impl error::Error for StreamDecodeError {}
+
+#[derive(Debug)]
+enum StreamDecodeErrorKind {
+    InvalidSignature { offset: usize, byte: u8 },
+    CorruptCrc { calculated: u32, expected: u32 },
+    /// A previous call to [`StreamDecoder::decode_next`] already returned
+    /// an error; the decoder's state no longer lines up with a chunk
+    /// boundary, so it refuses to make further progress.
+    Poisoned,
+}
+
+impl Display for StreamDecodeErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StreamDecodeErrorKind::InvalidSignature { offset, byte } => {
+                write!(f, "invalid png signature byte `{byte}` at offset {offset}")
+            }
+            StreamDecodeErrorKind::CorruptCrc {
+                calculated,
+                expected,
+            } => write!(f, "invalid crc: expected: {expected}, got {calculated}"),
+            StreamDecodeErrorKind::Poisoned => {
+                write!(f, "decoder already returned an error and cannot continue")
+            }
+        }
+    }
+}
+
+impl error::Error for StreamDecodeErrorKind {}
+
+/// A critical or ancillary chunk this crate knows how to interpret,
+/// decoded from its raw bytes. Any chunk type not listed here stays as
+/// opaque [`Chunk`] bytes.
+pub enum StandardChunk {
+    Ihdr(Ihdr),
+    Phys(Phys),
+    Actl(Actl),
+    Fctl(Fctl),
+}
+
+impl StandardChunk {
+    /// Decodes `chunk` if its type is one this crate recognizes, or
+    /// `None` for any other chunk type, critical ones such as `IDAT` included.
+    pub fn parse(
+        chunk: &Chunk,
+    ) -> Option<std::result::Result<StandardChunk, StandardChunkError>> {
+        match chunk.chunk_type().to_string().as_str() {
+            "IHDR" => Some(Ihdr::try_from(chunk).map(StandardChunk::Ihdr)),
+            "pHYs" => Some(Phys::try_from(chunk).map(StandardChunk::Phys)),
+            "acTL" => Some(Actl::try_from(chunk).map(StandardChunk::Actl)),
+            "fcTL" => Some(Fctl::try_from(chunk).map(StandardChunk::Fctl)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for StandardChunk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StandardChunk::Ihdr(ihdr) => write!(
+                f,
+                "IHDR: {}x{} px, bit depth {}, color type {}",
+                ihdr.width, ihdr.height, ihdr.bit_depth, ihdr.color_type
+            ),
+            StandardChunk::Phys(phys) => write!(
+                f,
+                "pHYs: {}x{} pixels per unit (unit {})",
+                phys.pixels_per_unit_x, phys.pixels_per_unit_y, phys.unit
+            ),
+            StandardChunk::Actl(actl) => {
+                write!(f, "acTL: {} frame(s), {} play(s)", actl.num_frames, actl.num_plays)
+            }
+            StandardChunk::Fctl(fctl) => write!(
+                f,
+                "fcTL: frame {}, {}x{} at ({}, {}), delay {}/{}",
+                fctl.sequence_number,
+                fctl.width,
+                fctl.height,
+                fctl.x_offset,
+                fctl.y_offset,
+                fctl.delay_num,
+                fctl.delay_den
+            ),
+        }
+    }
+}
+
+/// Decoded `IHDR` chunk: the image header, always the first chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ihdr {
+    pub width: u32,
+    pub height: u32,
+    pub bit_depth: u8,
+    pub color_type: u8,
+    pub compression: u8,
+    pub filter: u8,
+    pub interlace: u8,
+}
+
+impl TryFrom<&Chunk> for Ihdr {
+    type Error = StandardChunkError;
+
+    fn try_from(chunk: &Chunk) -> std::result::Result<Self, Self::Error> {
+        let data = standard_chunk_data(chunk, "IHDR", 13)?;
+        let mut cursor = ByteCursor::new(data);
+        Ok(Self {
+            width: cursor.u32_be().expect("length checked above"),
+            height: cursor.u32_be().expect("length checked above"),
+            bit_depth: cursor.take(1).expect("length checked above")[0],
+            color_type: cursor.take(1).expect("length checked above")[0],
+            compression: cursor.take(1).expect("length checked above")[0],
+            filter: cursor.take(1).expect("length checked above")[0],
+            interlace: cursor.take(1).expect("length checked above")[0],
+        })
+    }
+}
+
+/// Decoded `pHYs` chunk: the intended pixel aspect ratio or resolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Phys {
+    pub pixels_per_unit_x: u32,
+    pub pixels_per_unit_y: u32,
+    pub unit: u8,
+}
+
+impl TryFrom<&Chunk> for Phys {
+    type Error = StandardChunkError;
+
+    fn try_from(chunk: &Chunk) -> std::result::Result<Self, Self::Error> {
+        let data = standard_chunk_data(chunk, "pHYs", 9)?;
+        let mut cursor = ByteCursor::new(data);
+        Ok(Self {
+            pixels_per_unit_x: cursor.u32_be().expect("length checked above"),
+            pixels_per_unit_y: cursor.u32_be().expect("length checked above"),
+            unit: cursor.take(1).expect("length checked above")[0],
+        })
+    }
+}
+
+/// Decoded `acTL` chunk: the APNG animation control header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Actl {
+    pub num_frames: u32,
+    pub num_plays: u32,
+}
+
+impl TryFrom<&Chunk> for Actl {
+    type Error = StandardChunkError;
+
+    fn try_from(chunk: &Chunk) -> std::result::Result<Self, Self::Error> {
+        let data = standard_chunk_data(chunk, "acTL", 8)?;
+        let mut cursor = ByteCursor::new(data);
+        Ok(Self {
+            num_frames: cursor.u32_be().expect("length checked above"),
+            num_plays: cursor.u32_be().expect("length checked above"),
+        })
+    }
+}
+
+/// Decoded `fcTL` chunk: one APNG frame's control data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fctl {
+    pub sequence_number: u32,
+    pub width: u32,
+    pub height: u32,
+    pub x_offset: u32,
+    pub y_offset: u32,
+    pub delay_num: u16,
+    pub delay_den: u16,
+    pub dispose_op: u8,
+    pub blend_op: u8,
+}
+
+impl TryFrom<&Chunk> for Fctl {
+    type Error = StandardChunkError;
+
+    fn try_from(chunk: &Chunk) -> std::result::Result<Self, Self::Error> {
+        let data = standard_chunk_data(chunk, "fcTL", 26)?;
+        let mut cursor = ByteCursor::new(data);
+        Ok(Self {
+            sequence_number: cursor.u32_be().expect("length checked above"),
+            width: cursor.u32_be().expect("length checked above"),
+            height: cursor.u32_be().expect("length checked above"),
+            x_offset: cursor.u32_be().expect("length checked above"),
+            y_offset: cursor.u32_be().expect("length checked above"),
+            delay_num: cursor.u16_be().expect("length checked above"),
+            delay_den: cursor.u16_be().expect("length checked above"),
+            dispose_op: cursor.take(1).expect("length checked above")[0],
+            blend_op: cursor.take(1).expect("length checked above")[0],
+        })
+    }
+}
+
+fn standard_chunk_data<'a>(
+    chunk: &'a Chunk,
+    expected_type: &'static str,
+    expected_len: usize,
+) -> std::result::Result<&'a [u8], StandardChunkError> {
+    if chunk.chunk_type().to_string() != expected_type {
+        return Err(StandardChunkError::new(StandardChunkErrorKind::WrongType {
+            expected: expected_type,
+            found: chunk.chunk_type().to_string(),
+        }));
+    }
+    if chunk.data().len() != expected_len {
+        return Err(StandardChunkError::new(StandardChunkErrorKind::WrongLength {
+            expected: expected_len,
+            found: chunk.data().len(),
+        }));
+    }
+    Ok(chunk.data())
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct StandardChunkError {
+    kind: StandardChunkErrorKind,
+}
+
+impl StandardChunkError {
+    fn new(kind: StandardChunkErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl Display for StandardChunkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.kind, f)
+    }
+}
+
+impl error::Error for StandardChunkError {}
+
+#[derive(Debug)]
+enum StandardChunkErrorKind {
+    WrongType {
+        expected: &'static str,
+        found: String,
+    },
+    WrongLength {
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for StandardChunkErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StandardChunkErrorKind::WrongType { expected, found } => {
+                write!(f, "expected a `{expected}` chunk, found `{found}`")
+            }
+            StandardChunkErrorKind::WrongLength { expected, found } => {
+                write!(f, "expected {expected} data bytes, found {found}")
+            }
+        }
+    }
+}
+
+impl error::Error for StandardChunkErrorKind {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_type::ChunkType;
+    use std::str::FromStr;
+
+    fn testing_chunks() -> Vec<Chunk> {
+        vec![
+            chunk_from_strings("FrSt", "I am the first chunk"),
+            chunk_from_strings("miDl", "I am another chunk"),
+            chunk_from_strings("LASt", "I am the last chunk"),
+        ]
+    }
+
+    fn chunk_from_strings(chunk_type: &str, data: &str) -> Chunk {
+        let chunk_type = ChunkType::from_str(chunk_type).unwrap();
+        let data: Vec<u8> = data.bytes().collect();
+
+        Chunk::new(chunk_type, data)
+    }
+
+    fn testing_png() -> Png {
+        let chunks = testing_chunks();
+        Png::from_chunks(chunks)
+    }
+
+    #[test]
+    fn test_from_chunks() {
+        let chunks = testing_chunks();
+        let png = Png::from_chunks(chunks);
+
+        assert_eq!(png.chunks().len(), 3);
+    }
+
+    #[test]
+    fn test_valid_from_bytes() {
+        let chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+
+        let bytes: Vec<u8> = STANDARD_HEADER
+            .iter()
+            .chain(chunk_bytes.iter())
+            .copied()
+            .collect();
+
+        let png = Png::try_from(bytes.as_ref()).unwrap();
+
+        assert_eq!(png.chunks().len(), 3);
+    }
+
+    #[test]
+    fn test_try_from_lossy_recovers_past_corrupt_chunk() {
+        let mut chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+        // Flip a byte inside the first chunk's data, corrupting its crc
+        // without touching the chunks after it.
+        chunk_bytes[10] ^= 0xFF;
+
+        let bytes: Vec<u8> = STANDARD_HEADER
+            .iter()
+            .chain(chunk_bytes.iter())
+            .copied()
+            .collect();
+
+        let (png, errors) = Png::try_from_lossy(&bytes);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(png.chunks().len(), 2);
+        assert_eq!(&png.chunks()[0].chunk_type().to_string(), "miDl");
+        assert_eq!(&png.chunks()[1].chunk_type().to_string(), "LASt");
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        let chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+
+        let bytes: Vec<u8> = [13, 80, 78, 71, 13, 10, 26, 10]
+            .iter()
+            .chain(chunk_bytes.iter())
+            .copied()
+            .collect();
+
+        let png = Png::try_from(bytes.as_ref());
+        assert!(png.is_err());
+    }
+
+    #[test]
+    fn test_invalid_chunk() {
+        let mut chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+        let last = chunk_bytes.len() - 1;
+        chunk_bytes[last] ^= 0xFF;
+
+        let bytes: Vec<u8> = STANDARD_HEADER
+            .iter()
+            .chain(chunk_bytes.iter())
+            .copied()
+            .collect();
+
+        let png = Png::try_from(bytes.as_ref());
+        assert!(png.is_err());
+    }
+
+    #[test]
+    fn test_truncated_chunk_reports_file_relative_offset() {
+        let mut bytes: Vec<u8> = STANDARD_HEADER.to_vec();
+        bytes.extend(testing_chunks()[0].as_bytes());
+        let second_chunk_offset = bytes.len();
+        bytes.extend(&testing_chunks()[1].as_bytes()[..3]);
+
+        let (_, errors) = Png::try_from_lossy(&bytes);
+        let [err] = errors.as_slice() else {
+            panic!("expected exactly one chunk error, got {errors:?}");
+        };
+        let message = std::error::Error::source(err)
+            .expect("NotEnoughBytes wraps a source")
+            .to_string();
+
+        assert!(message.contains(&format!("offset {second_chunk_offset}")));
+    }
+
+    #[test]
+    fn test_list_chunks() {
+        let png = testing_png();
+        let chunks = png.chunks();
+
+        assert_eq!(chunks.len(), 3);
+    }
+
+    #[test]
+    fn test_chunk_by_type() {
+        let png = testing_png();
+        let chunk = png.chunk_by_type("FrSt").unwrap();
+
+        assert_eq!(&chunk.chunk_type().to_string(), "FrSt");
+        assert_eq!(&chunk.data_as_string().unwrap(), "I am the first chunk");
+    }
+
+    #[test]
+    fn test_append_chunk() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("TeSt", "Message"));
+        let chunk = png.chunk_by_type("TeSt").unwrap();
+
+        assert_eq!(&chunk.chunk_type().to_string(), "TeSt");
+        assert_eq!(&chunk.data_as_string().unwrap(), "Message");
+    }
+
+    #[test]
+    fn test_remove_chunk() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("TeSt", "Message"));
+        png.remove_first_chunk("TeSt").unwrap();
+        let chunk = png.chunk_by_type("TeSt");
+
+        assert!(chunk.is_none());
+    }
+
+    #[test]
+    fn test_png_trait_impls() {
+        let chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+
+        let bytes: Vec<u8> = STANDARD_HEADER
+            .iter()
+            .chain(chunk_bytes.iter())
+            .copied()
+            .collect();
+
+        let png: Png = TryFrom::try_from(bytes.as_ref()).unwrap();
+
+        let _png_string = format!("{png}", png = png.chunks().len());
+    }
+
+    #[test]
+    fn test_write_to_matches_as_bytes() {
+        let png = testing_png();
+        let mut written = Vec::new();
+        png.write_to(&mut written).unwrap();
+
+        assert_eq!(written, png.as_bytes());
+    }
+
+    fn ihdr_chunk() -> Chunk {
+        let mut data = Vec::new();
+        data.extend_from_slice(&100u32.to_be_bytes());
+        data.extend_from_slice(&50u32.to_be_bytes());
+        data.extend_from_slice(&[8, 6, 0, 0, 0]);
+        Chunk::new(ChunkType::from_str("IHDR").unwrap(), data)
+    }
+
+    #[test]
+    fn test_parse_ihdr() {
+        let chunk = ihdr_chunk();
+        let ihdr = Ihdr::try_from(&chunk).unwrap();
+
+        assert_eq!(ihdr.width, 100);
+        assert_eq!(ihdr.height, 50);
+        assert_eq!(ihdr.bit_depth, 8);
+        assert_eq!(ihdr.color_type, 6);
+    }
+
+    #[test]
+    fn test_parse_ihdr_wrong_length() {
+        let chunk = Chunk::new(ChunkType::from_str("IHDR").unwrap(), vec![0; 4]);
+        assert!(Ihdr::try_from(&chunk).is_err());
+    }
+
+    #[test]
+    fn test_parse_phys() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&2835u32.to_be_bytes());
+        data.extend_from_slice(&2835u32.to_be_bytes());
+        data.push(1);
+        let chunk = Chunk::new(ChunkType::from_str("pHYs").unwrap(), data);
+
+        let phys = Phys::try_from(&chunk).unwrap();
+        assert_eq!(phys.pixels_per_unit_x, 2835);
+        assert_eq!(phys.unit, 1);
+    }
+
+    #[test]
+    fn test_parse_actl() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&3u32.to_be_bytes());
+        data.extend_from_slice(&0u32.to_be_bytes());
+        let chunk = Chunk::new(ChunkType::from_str("acTL").unwrap(), data);
+
+        let actl = Actl::try_from(&chunk).unwrap();
+        assert_eq!(actl.num_frames, 3);
+        assert_eq!(actl.num_plays, 0);
+    }
+
+    #[test]
+    fn test_parse_fctl() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&1u32.to_be_bytes());
+        data.extend_from_slice(&100u32.to_be_bytes());
+        data.extend_from_slice(&50u32.to_be_bytes());
+        data.extend_from_slice(&0u32.to_be_bytes());
+        data.extend_from_slice(&0u32.to_be_bytes());
+        data.extend_from_slice(&100u16.to_be_bytes());
+        data.extend_from_slice(&100u16.to_be_bytes());
+        data.push(0);
+        data.push(0);
+        let chunk = Chunk::new(ChunkType::from_str("fcTL").unwrap(), data);
+
+        let fctl = Fctl::try_from(&chunk).unwrap();
+        assert_eq!(fctl.sequence_number, 1);
+        assert_eq!(fctl.width, 100);
+        assert_eq!(fctl.delay_num, 100);
+    }
+
+    #[test]
+    fn test_standard_chunk_parse_unknown_type_is_none() {
+        let chunk = chunk_from_strings("RuSt", "not a standard chunk");
+        assert!(StandardChunk::parse(&chunk).is_none());
+    }
+
+    #[test]
+    fn test_png_header() {
+        let mut png = Png::from_chunks(vec![ihdr_chunk()]);
+        png.append_chunk(chunk_from_strings("IEND", ""));
+
+        let ihdr = png.header().unwrap();
+        assert_eq!(ihdr.width, 100);
+    }
+
+    #[test]
+    fn test_png_header_missing_ihdr() {
+        let png = testing_png();
+        assert!(png.header().is_err());
+    }
+
+    fn testing_png_bytes() -> Vec<u8> {
+        let chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+
+        STANDARD_HEADER
+            .iter()
+            .chain(chunk_bytes.iter())
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn test_stream_decoder_whole_input_at_once() {
+        let bytes = testing_png_bytes();
+        let mut decoder = StreamDecoder::new();
+
+        let mut offset = 0;
+        let mut completed = Vec::new();
+        loop {
+            let (consumed, decoded) = decoder.decode_next(&bytes[offset..]).unwrap();
+            offset += consumed;
+            match decoded {
+                Decoded::ChunkComplete(chunk) => completed.push(chunk),
+                Decoded::Nothing => break,
+                Decoded::ChunkBegin { .. } | Decoded::ImageEnd => {}
+            }
+        }
+
+        assert_eq!(completed.len(), 3);
+        assert_eq!(&completed[0].chunk_type().to_string(), "FrSt");
+    }
+
+    #[test]
+    fn test_stream_decoder_byte_at_a_time() {
+        let bytes = testing_png_bytes();
+        let mut decoder = StreamDecoder::new();
+
+        let mut completed = Vec::new();
+        let mut saw_image_end = false;
+        for byte in &bytes {
+            let (consumed, decoded) = decoder.decode_next(std::slice::from_ref(byte)).unwrap();
+            assert_eq!(consumed, 1);
+            match decoded {
+                Decoded::ChunkComplete(chunk) => completed.push(chunk),
+                Decoded::ImageEnd => saw_image_end = true,
+                Decoded::Nothing | Decoded::ChunkBegin { .. } => {}
+            }
+        }
+
+        assert_eq!(completed.len(), 3);
+        assert!(!saw_image_end, "testing chunks contain no IEND");
+    }
+
+    #[test]
+    fn test_stream_decoder_invalid_signature() {
+        let mut bytes = testing_png_bytes();
+        bytes[0] = 0;
+        let mut decoder = StreamDecoder::new();
+
+        assert!(decoder.decode_next(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_stream_decoder_corrupt_crc() {
+        let mut bytes = testing_png_bytes();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        let mut decoder = StreamDecoder::new();
+
+        let mut result = Ok((0, Decoded::Nothing));
+        let mut offset = 0;
+        while offset < bytes.len() {
+            result = decoder.decode_next(&bytes[offset..]);
+            match &result {
+                Ok((consumed, _)) => offset += consumed,
+                Err(_) => break,
+            }
+        }
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_stream_decoder_poisoned_after_error() {
+        let mut bytes = testing_png_bytes();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        let mut decoder = StreamDecoder::new();
+
+        while let Ok((consumed, _)) = decoder.decode_next(&bytes) {
+            bytes.drain(..consumed);
+        }
+
+        let valid_chunk = Chunk::new(ChunkType::try_from(*b"RuSt").unwrap(), b"hi".to_vec());
+        let more_bytes: Vec<u8> = valid_chunk.as_bytes();
+        assert!(decoder.decode_next(&more_bytes).is_err());
+    }
+}